@@ -0,0 +1,203 @@
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::driver::{LensDriver, LensError, Result};
+
+/// One measurement used to fit a [`CalibrationModel`]: the current commanded
+/// at the time of measurement, the lens temperature, and the focal power
+/// actually observed downstream (e.g. with a wavefront sensor).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CalibrationSample {
+    pub current_ma: f64,
+    pub temperature_c: f64,
+    pub diopter: f64,
+}
+
+/// Linear temperature-drift model fit from calibration samples:
+/// `diopter = a * current_ma + b * temperature_c + c`.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct CalibrationModel {
+    pub a: f64,
+    pub b: f64,
+    pub c: f64,
+    pub firmware_version: (u8, u8, u16, u16),
+    pub fit_unix_time: u64,
+}
+
+impl CalibrationModel {
+    /// Fit `a`, `b`, `c` by solving the 3-parameter least-squares normal
+    /// equations over `samples`.
+    pub fn fit(samples: &[CalibrationSample], firmware_version: (u8, u8, u16, u16)) -> Result<Self> {
+        if samples.len() < 3 {
+            return Err(LensError::Calibration(format!(
+                "need at least 3 calibration samples, got {}",
+                samples.len()
+            )));
+        }
+
+        let n = samples.len() as f64;
+        let mut sxx = 0.0;
+        let mut sxt = 0.0;
+        let mut sx = 0.0;
+        let mut stt = 0.0;
+        let mut st = 0.0;
+        let mut sxd = 0.0;
+        let mut std_ = 0.0;
+        let mut sd = 0.0;
+
+        for sample in samples {
+            let i = sample.current_ma;
+            let t = sample.temperature_c;
+            let d = sample.diopter;
+
+            sxx += i * i;
+            sxt += i * t;
+            sx += i;
+            stt += t * t;
+            st += t;
+            sxd += i * d;
+            std_ += t * d;
+            sd += d;
+        }
+
+        // Solve the 3x3 normal-equations system via Cramer's rule:
+        //   [sxx sxt sx] [a]   [sxd]
+        //   [sxt stt st] [b] = [std]
+        //   [sx  st  n ] [c]   [sd ]
+        let det = det3(
+            [sxx, sxt, sx],
+            [sxt, stt, st],
+            [sx, st, n],
+        );
+
+        if det.abs() < 1e-12 {
+            return Err(LensError::Calibration(
+                "calibration samples are degenerate (singular system)".to_string(),
+            ));
+        }
+
+        let a = det3([sxd, sxt, sx], [std_, stt, st], [sd, st, n]) / det;
+        let b = det3([sxx, sxd, sx], [sxt, std_, st], [sx, sd, n]) / det;
+        let c = det3([sxx, sxt, sxd], [sxt, stt, std_], [sx, st, sd]) / det;
+
+        Ok(CalibrationModel {
+            a,
+            b,
+            c,
+            firmware_version,
+            fit_unix_time: unix_time(),
+        })
+    }
+
+    /// Invert the model to find the current that should produce `target`
+    /// diopters at `temperature_c`.
+    fn current_for(&self, target: f64, temperature_c: f64) -> Result<f64> {
+        if self.a.abs() < 1e-12 {
+            return Err(LensError::Calibration(
+                "calibration model has a zero current coefficient".to_string(),
+            ));
+        }
+
+        Ok((target - self.b * temperature_c - self.c) / self.a)
+    }
+}
+
+fn det3(row0: [f64; 3], row1: [f64; 3], row2: [f64; 3]) -> f64 {
+    row0[0] * (row1[1] * row2[2] - row1[2] * row2[1])
+        - row0[1] * (row1[0] * row2[2] - row1[2] * row2[0])
+        + row0[2] * (row1[0] * row2[1] - row1[1] * row2[0])
+}
+
+fn unix_time() -> u64 {
+    use std::time::{SystemTime, UNIX_EPOCH};
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+impl LensDriver {
+    /// Fit a [`CalibrationModel`] from `samples` and store it for use by
+    /// `set_diopter_compensated`.
+    pub fn calibrate(&mut self, samples: &[CalibrationSample]) -> Result<()> {
+        let model = CalibrationModel::fit(samples, self.firmware_version())?;
+        self.calibration = Some(model);
+        Ok(())
+    }
+
+    /// Load a previously saved calibration model from `path`.
+    pub fn load_calibration(&mut self, path: impl AsRef<Path>) -> Result<()> {
+        let contents = fs::read_to_string(path)?;
+        let model: CalibrationModel = serde_json::from_str(&contents)?;
+        self.calibration = Some(model);
+        Ok(())
+    }
+
+    /// Save the currently fit calibration model to `path` as JSON.
+    pub fn save_calibration(&self, path: impl AsRef<Path>) -> Result<()> {
+        let model = self.calibration.ok_or_else(|| {
+            LensError::Calibration("no calibration model to save; call calibrate() first".to_string())
+        })?;
+        let contents = serde_json::to_string_pretty(&model)?;
+        fs::write(path, contents)?;
+        Ok(())
+    }
+
+    /// Set the focal power in diopters, compensating for the current lens
+    /// temperature using the stored calibration model.
+    pub fn set_diopter_compensated(&mut self, target: f64) -> Result<()> {
+        let model = self.calibration.ok_or_else(|| {
+            LensError::Calibration("no calibration model loaded; call calibrate() or load_calibration() first".to_string())
+        })?;
+
+        let temperature = self.get_temperature()?;
+        let current = model.current_for(target, temperature)?;
+        self.set_current(current)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fit_recovers_known_linear_coefficients() {
+        // diopter = 0.01 * current_ma + 0.05 * temperature_c - 2.0
+        let a = 0.01;
+        let b = 0.05;
+        let c = -2.0;
+
+        let samples: Vec<CalibrationSample> = [
+            (0.0, 20.0),
+            (100.0, 20.0),
+            (200.0, 25.0),
+            (300.0, 30.0),
+            (400.0, 22.0),
+        ]
+        .iter()
+        .map(|&(current_ma, temperature_c)| CalibrationSample {
+            current_ma,
+            temperature_c,
+            diopter: a * current_ma + b * temperature_c + c,
+        })
+        .collect();
+
+        let model = CalibrationModel::fit(&samples, (1, 0, 0, 0)).unwrap();
+
+        assert!((model.a - a).abs() < 1e-9);
+        assert!((model.b - b).abs() < 1e-9);
+        assert!((model.c - c).abs() < 1e-9);
+    }
+
+    #[test]
+    fn fit_rejects_too_few_samples() {
+        let samples = [
+            CalibrationSample { current_ma: 0.0, temperature_c: 20.0, diopter: 0.0 },
+            CalibrationSample { current_ma: 100.0, temperature_c: 20.0, diopter: 1.0 },
+        ];
+
+        assert!(CalibrationModel::fit(&samples, (1, 0, 0, 0)).is_err());
+    }
+}
@@ -0,0 +1,161 @@
+/// A single typed device command. Each variant knows how to encode itself
+/// into the wire payload and how many reply bytes to expect back, so framing
+/// and CRC appending live in one place instead of being re-implemented at
+/// every call site.
+#[derive(Clone, Debug)]
+pub(crate) enum Command {
+    GetFirmwareType,
+    GetFirmwareVersion,
+    GetMaxOutputCurrent,
+    GetTemperature,
+    GetCurrentLimits,
+    SetCurrentLimits { lower_raw: i16, upper_raw: i16 },
+    GetTempLimits,
+    SetTempLimits { lower_raw: i16, upper_raw: i16 },
+    SetModeCurrent,
+    SetModeFocalPower,
+    GetActiveMode,
+    GetCurrent,
+    SetCurrent { raw: i16 },
+    GetDiopter,
+    SetDiopter { raw: i16 },
+    GetPartNumber,
+    GetSerialNumber,
+    EnterBootloader,
+    FlashBlock { index: u16, data: Vec<u8> },
+    GetBootloaderState,
+    GetStatusRegister,
+}
+
+impl Command {
+    /// The raw bytes sent over the wire, before the CRC is appended.
+    pub(crate) fn payload(&self) -> Vec<u8> {
+        match self {
+            Command::GetFirmwareType => b"H".to_vec(),
+            Command::GetFirmwareVersion => b"V\x00".to_vec(),
+            Command::GetMaxOutputCurrent => b"CrMA\x00\x00".to_vec(),
+            Command::GetTemperature => b"TCA".to_vec(),
+            Command::GetCurrentLimits => b"CrLA\x00\x00\x00\x00".to_vec(),
+            Command::SetCurrentLimits { lower_raw, upper_raw } => {
+                let mut payload = b"CwLA".to_vec();
+                payload.extend_from_slice(&lower_raw.to_be_bytes());
+                payload.extend_from_slice(&upper_raw.to_be_bytes());
+                payload
+            }
+            Command::GetTempLimits => b"TrLA\x00\x00\x00\x00".to_vec(),
+            Command::SetTempLimits { lower_raw, upper_raw } => {
+                let mut payload = b"TwLA".to_vec();
+                payload.extend_from_slice(&lower_raw.to_be_bytes());
+                payload.extend_from_slice(&upper_raw.to_be_bytes());
+                payload
+            }
+            Command::SetModeCurrent => b"MwDA".to_vec(),
+            Command::SetModeFocalPower => b"MwCA".to_vec(),
+            Command::GetActiveMode => b"MMA".to_vec(),
+            Command::GetCurrent => b"Ar\x00\x00".to_vec(),
+            Command::SetCurrent { raw } => {
+                let mut payload = b"Aw".to_vec();
+                payload.extend_from_slice(&raw.to_be_bytes());
+                payload
+            }
+            Command::GetDiopter => b"PrDA\x00\x00\x00\x00".to_vec(),
+            Command::SetDiopter { raw } => {
+                let mut payload = b"PwDA".to_vec();
+                payload.extend_from_slice(&raw.to_be_bytes());
+                payload.extend_from_slice(&[0, 0]);
+                payload
+            }
+            Command::GetPartNumber => b"PN".to_vec(),
+            Command::GetSerialNumber => b"SN".to_vec(),
+            Command::EnterBootloader => b"BLDR".to_vec(),
+            Command::FlashBlock { index, data } => {
+                let mut payload = b"FwBl".to_vec();
+                payload.extend_from_slice(&index.to_be_bytes());
+                payload.extend_from_slice(data);
+                payload
+            }
+            Command::GetBootloaderState => b"BLST".to_vec(),
+            Command::GetStatusRegister => b"ErSA\x00\x00".to_vec(),
+        }
+    }
+
+    /// Number of reply bytes expected back, not counting the trailing
+    /// CRC + `\r\n`.
+    pub(crate) fn reply_len(&self) -> usize {
+        match self {
+            Command::GetFirmwareType => 1,
+            Command::GetFirmwareVersion => 6,
+            Command::GetMaxOutputCurrent => 2,
+            Command::GetTemperature => 2,
+            Command::GetCurrentLimits => 4,
+            Command::SetCurrentLimits { .. } => 0,
+            Command::GetTempLimits => 4,
+            Command::SetTempLimits { .. } => 0,
+            Command::SetModeCurrent => 0,
+            Command::SetModeFocalPower => 5,
+            Command::GetActiveMode => 1,
+            Command::GetCurrent => 2,
+            Command::SetCurrent { .. } => 0,
+            Command::GetDiopter => 2,
+            Command::SetDiopter { .. } => 0,
+            Command::GetPartNumber => 16,
+            Command::GetSerialNumber => 16,
+            Command::EnterBootloader => 0,
+            Command::FlashBlock { .. } => 1,
+            Command::GetBootloaderState => 1,
+            Command::GetStatusRegister => 2,
+        }
+    }
+}
+
+/// A CRC-checked, framing-validated reply body, decoded into typed values.
+#[derive(Clone, Debug)]
+pub(crate) struct Response(Vec<u8>);
+
+impl Response {
+    pub(crate) fn new(data: Vec<u8>) -> Self {
+        Response(data)
+    }
+
+    pub(crate) fn as_u8(&self) -> u8 {
+        self.0[0]
+    }
+
+    pub(crate) fn as_i16(&self) -> i16 {
+        i16::from_be_bytes([self.0[0], self.0[1]])
+    }
+
+    /// Decode a `[hi, lo, hi, lo]` reply as two consecutive `i16`s.
+    pub(crate) fn as_i16_pair(&self) -> (i16, i16) {
+        (
+            i16::from_be_bytes([self.0[0], self.0[1]]),
+            i16::from_be_bytes([self.0[2], self.0[3]]),
+        )
+    }
+
+    /// Decode the `set_mode(FocalPower)` reply layout of
+    /// `[mode_echo, max_hi, max_lo, min_hi, min_lo]` into `(min, max)`.
+    pub(crate) fn as_focal_power_range(&self) -> (i16, i16) {
+        (
+            i16::from_be_bytes([self.0[3], self.0[4]]),
+            i16::from_be_bytes([self.0[1], self.0[2]]),
+        )
+    }
+
+    pub(crate) fn as_firmware_version(&self) -> (u8, u8, u16, u16) {
+        (
+            self.0[0],
+            self.0[1],
+            u16::from_be_bytes([self.0[2], self.0[3]]),
+            u16::from_be_bytes([self.0[4], self.0[5]]),
+        )
+    }
+
+    pub(crate) fn as_ascii(&self) -> String {
+        String::from_utf8_lossy(&self.0).to_string()
+    }
+
+    pub(crate) fn as_ascii_trimmed(&self) -> String {
+        String::from_utf8_lossy(&self.0).trim_end().to_string()
+    }
+}
@@ -0,0 +1,120 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Instant;
+
+use crate::command::Command;
+use crate::driver::{LensDriver, Result};
+
+/// Decoded contents of the controller's status/fault register.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct FaultFlags {
+    pub over_temperature: bool,
+    pub over_current: bool,
+    pub undervoltage: bool,
+}
+
+impl FaultFlags {
+    fn from_bits(bits: u16) -> Self {
+        FaultFlags {
+            over_temperature: bits & 0x01 != 0,
+            over_current: bits & 0x02 != 0,
+            undervoltage: bits & 0x04 != 0,
+        }
+    }
+
+    /// Human-readable descriptions of every flag currently set.
+    pub fn descriptions(&self) -> Vec<&'static str> {
+        let mut descriptions = Vec::new();
+        if self.over_temperature {
+            descriptions.push("Over-temperature: lens driver exceeded its safe operating temperature");
+        }
+        if self.over_current {
+            descriptions.push("Over-current: commanded current exceeded the driver's safe limit");
+        }
+        if self.undervoltage {
+            descriptions.push("Undervoltage: supply voltage dropped below the driver's operating threshold");
+        }
+        descriptions
+    }
+}
+
+/// Diagnostic report combining the device's decoded fault flags with the
+/// driver's own handshake/CRC failure counters, turning opaque runtime
+/// errors into something actionable.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FaultReport {
+    pub flags: FaultFlags,
+    pub handshake_failures: u64,
+    pub crc_failures: u64,
+}
+
+/// Kind of event recorded in the driver's in-memory event log.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EventType {
+    ModeChange,
+    CrcFailure,
+    FaultRaised,
+    FaultCleared,
+}
+
+/// One entry in the event log: when it happened (seconds since the log was
+/// created), what happened, where it came from, and which run (device
+/// session) it belongs to.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EventLogEntry {
+    pub timestamp: f64,
+    pub event_type: EventType,
+    pub source: String,
+    pub run_number: u64,
+}
+
+static NEXT_RUN_NUMBER: AtomicU64 = AtomicU64::new(1);
+
+/// In-memory log of mode changes, CRC failures, and fault transitions for
+/// one driver session ("run").
+pub(crate) struct EventLog {
+    run_number: u64,
+    start: Instant,
+    entries: Vec<EventLogEntry>,
+}
+
+impl EventLog {
+    pub(crate) fn new() -> Self {
+        EventLog {
+            run_number: NEXT_RUN_NUMBER.fetch_add(1, Ordering::Relaxed),
+            start: Instant::now(),
+            entries: Vec::new(),
+        }
+    }
+
+    pub(crate) fn record(&mut self, event_type: EventType, source: impl Into<String>) {
+        self.entries.push(EventLogEntry {
+            timestamp: self.start.elapsed().as_secs_f64(),
+            event_type,
+            source: source.into(),
+            run_number: self.run_number,
+        });
+    }
+
+    pub(crate) fn entries(&self) -> &[EventLogEntry] {
+        &self.entries
+    }
+}
+
+impl LensDriver {
+    /// Read and decode the controller's status/fault register, combined
+    /// with the driver's own handshake/CRC failure counters.
+    pub fn fault_report(&mut self) -> Result<FaultReport> {
+        let response = self.send_command(&Command::GetStatusRegister)?;
+        Ok(FaultReport {
+            flags: FaultFlags::from_bits(response.as_i16() as u16),
+            handshake_failures: self.handshake_failures,
+            crc_failures: self.crc_failures,
+        })
+    }
+
+    /// Snapshot of the mode-change/CRC-failure/fault-transition event log
+    /// recorded so far for this driver session.
+    pub fn get_event_log(&self) -> &[EventLogEntry] {
+        self.event_log.entries()
+    }
+}
@@ -1,11 +1,14 @@
 use serialport::{SerialPort, new};
-use std::time::Duration;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use std::thread;
 use log::{debug, info, error};
 use num_derive::FromPrimitive;
 use num_traits::FromPrimitive;
 use thiserror::Error;
 
+use crate::command::{Command, Response};
+
 #[derive(Error, Debug)]
 pub enum LensError {
     #[error("Serial port error: {0}")]
@@ -28,26 +31,140 @@ pub enum LensError {
         expected: LensMode,
         actual: Option<LensMode>,
     },
+
+    #[error("Safety guard tripped: {reason}")]
+    SafetyTripped { reason: String },
+
+    #[error("Calibration JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("Calibration error: {0}")]
+    Calibration(String),
 }
 
 pub type Result<T> = std::result::Result<T, LensError>;
 
 #[derive(FromPrimitive, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(u8)]
 pub enum LensMode {
     Current = 1,
     FocalPower = 5,
 }
 
+/// A full snapshot of the device's state, suitable for logging as JSON or
+/// forwarding over a socket for telemetry.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LensStatus {
+    pub mode: Option<LensMode>,
+    pub temperature: f64,
+    pub current_ma: f64,
+    pub diopter: Option<f64>,
+    pub max_output_current: f64,
+    pub firmware_type: String,
+    pub firmware_version: (u8, u8, u16, u16),
+    pub focal_power_range: Option<(f64, f64)>,
+}
+
+/// Linear feed-forward model for focal-power drift with temperature:
+/// `d_corr = d - k * (temperature - t_ref)`.
+#[derive(Clone, Copy, Debug)]
+struct TempCompensation {
+    t_ref: f64,
+    k: f64,
+}
+
+/// Caps the rate of change of `set_current`/`set_diopter` to `rate` per
+/// second of elapsed wall time, stepping toward the target rather than
+/// jumping directly to it.
+#[derive(Clone, Copy, Debug)]
+struct SlewLimiter {
+    rate: f64,
+    last_value: Option<f64>,
+    last_time: Option<Instant>,
+}
+
+impl SlewLimiter {
+    /// Step `last_value` toward `target` by at most `rate * (now - last_time)`.
+    /// The first call after construction (or after a reseed) has no prior
+    /// value/time to step from, so it seeds them and passes `target` through
+    /// unlimited.
+    fn apply(&mut self, target: f64, now: Instant) -> f64 {
+        let (last_value, last_time) = match (self.last_value, self.last_time) {
+            (Some(value), Some(time)) => (value, time),
+            _ => {
+                self.last_value = Some(target);
+                self.last_time = Some(now);
+                return target;
+            }
+        };
+
+        let max_delta = self.rate * now.duration_since(last_time).as_secs_f64();
+        let delta = target - last_value;
+        let limited = if delta.abs() > max_delta {
+            last_value + max_delta.copysign(delta)
+        } else {
+            target
+        };
+
+        self.last_value = Some(limited);
+        self.last_time = Some(now);
+        limited
+    }
+}
+
+/// Identity of a lens controller discovered on a serial port
+#[derive(Clone, Debug)]
+pub struct LensInfo {
+    pub port_name: String,
+    pub serial_number: String,
+    pub part_number: String,
+    pub firmware_type: String,
+    pub firmware_version: (u8, u8, u16, u16),
+    pub max_output_current: f64,
+}
+
 pub struct LensDriver {
     port: Box<dyn SerialPort>,
     firmware_type: String,
     firmware_version: (u8, u8, u16, u16),
     max_output_current: f64,
     mode: Option<LensMode>,
+    focal_power_range: Option<(f64, f64)>,
+    temp_compensation: Option<TempCompensation>,
+    last_logical_diopter: Option<f64>,
+    fault: Option<String>,
+    calibration: Option<crate::calibration::CalibrationModel>,
+    slew: Option<SlewLimiter>,
+    handshake_failures: u64,
+    crc_failures: u64,
+    event_log: crate::diagnostics::EventLog,
 }
 
 impl LensDriver {
+    /// Build a driver around an already-open port, with every field at its
+    /// pre-handshake default. Shared by `new()` and `enumerate()` so a new
+    /// field only has to be added here once.
+    fn from_port(port: Box<dyn SerialPort>) -> Self {
+        LensDriver {
+            port,
+            firmware_type: String::new(),
+            firmware_version: (0, 0, 0, 0),
+            max_output_current: 0.0,
+            mode: None,
+            focal_power_range: None,
+            temp_compensation: None,
+            last_logical_diopter: None,
+            fault: None,
+            calibration: None,
+            slew: None,
+            handshake_failures: 0,
+            crc_failures: 0,
+            event_log: crate::diagnostics::EventLog::new(),
+        }
+    }
+
     pub fn new(port_name: &str, debug: bool) -> Result<Self> {
         if debug {
             env_logger::try_init().ok();
@@ -59,13 +176,7 @@ impl LensDriver {
 
         let port = new(port_name, 115200).timeout(Duration::from_secs(1)).open()?;
 
-        let mut driver = LensDriver {
-            port,
-            firmware_type: String::new(),
-            firmware_version: (0, 0, 0, 0),
-            max_output_current: 0.0,
-            mode: None,
-        };
+        let mut driver = Self::from_port(port);
 
         driver.handshake()?;
         driver.init()?;
@@ -73,14 +184,109 @@ impl LensDriver {
         Ok(driver)
     }
 
+    /// Scan all available serial ports for connected lens controllers.
+    ///
+    /// Each candidate port is opened at 115200 baud and put through the
+    /// handshake; ports that don't answer (other hardware, already in use,
+    /// etc.) are skipped rather than treated as an error.
+    pub fn enumerate() -> Result<Vec<LensInfo>> {
+        let mut found = Vec::new();
+
+        for port_info in serialport::available_ports()? {
+            let port_name = port_info.port_name;
+
+            let candidate = new(&port_name, 115200)
+                .timeout(Duration::from_millis(200))
+                .open();
+
+            let mut driver = match candidate {
+                Ok(port) => Self::from_port(port),
+                Err(e) => {
+                    debug!("Skipping {}: {}", port_name, e);
+                    continue;
+                }
+            };
+
+            if driver.handshake().is_err() {
+                debug!("Skipping {}: no handshake response", port_name);
+                continue;
+            }
+
+            let firmware_type = match driver.get_firmware_type() {
+                Ok(ft) => ft,
+                Err(_) => continue,
+            };
+            let part_number = match driver.get_part_number() {
+                Ok(pn) => pn,
+                Err(_) => continue,
+            };
+            let serial_number = match driver.get_serial_number() {
+                Ok(sn) => sn,
+                Err(_) => continue,
+            };
+            let firmware_version = match driver.get_firmware_version() {
+                Ok(fv) => fv,
+                Err(_) => continue,
+            };
+            let max_output_current = match driver.get_max_output_current() {
+                Ok(moc) => moc,
+                Err(_) => continue,
+            };
+
+            info!("Found lens controller on {}", port_name);
+            found.push(LensInfo {
+                port_name,
+                serial_number,
+                part_number,
+                firmware_type,
+                firmware_version,
+                max_output_current,
+            });
+        }
+
+        Ok(found)
+    }
+
+    /// Connect to the first lens controller whose reported serial number
+    /// matches `serial`, identifying hardware by its identity rather than
+    /// a fragile, udev-assigned path.
+    pub fn open_by_serial(serial: &str, debug: bool) -> Result<Self> {
+        let info = Self::enumerate()?
+            .into_iter()
+            .find(|info| info.serial_number == serial)
+            .ok_or(LensError::HandshakeFailed)?;
+
+        Self::new(&info.port_name, debug)
+    }
+
+    fn get_part_number(&mut self) -> Result<String> {
+        debug!("Getting part number");
+        let response = self.send_command(&Command::GetPartNumber)?;
+        Ok(response.as_ascii_trimmed())
+    }
+
+    fn get_serial_number(&mut self) -> Result<String> {
+        debug!("Getting serial number");
+        let response = self.send_command(&Command::GetSerialNumber)?;
+        Ok(response.as_ascii_trimmed())
+    }
+
     fn handshake(&mut self) -> Result<()> {
+        self.raw_handshake(b"Ready\r\n")
+    }
+
+    /// Perform the `"Start"` handshake, expecting back `expected` verbatim.
+    /// Used both for the normal application handshake and for re-handshaking
+    /// in bootloader/DFU mode, which replies with a different greeting.
+    pub(crate) fn raw_handshake(&mut self, expected: &[u8]) -> Result<()> {
         debug!("Performing handshake");
         self.port.write_all(b"Start")?;
 
-        let mut response = [0u8; 7];
+        let mut response = vec![0u8; expected.len()];
         self.port.read_exact(&mut response)?;
 
-        if &response != b"Ready\r\n" {
+        if response != expected {
+            self.handshake_failures += 1;
             return Err(LensError::HandshakeFailed);
         }
 
@@ -115,62 +321,137 @@ impl LensDriver {
     pub fn max_output_current(&self) -> f64 {
         self.max_output_current
     }
-    
+
+    /// Get the `(min, max)` diopter range for the currently active focal
+    /// power mode, as returned by the last `set_mode(FocalPower)` call.
+    pub fn focal_power_range(&self) -> Option<(f64, f64)> {
+        self.focal_power_range
+    }
+
+
     fn get_firmware_type(&mut self) -> Result<String> {
         debug!("Getting firmware type");
-        let response = self.send_command(b"H", 1)?;
-        Ok(String::from_utf8_lossy(&response).to_string())
+        let response = self.send_command(&Command::GetFirmwareType)?;
+        Ok(response.as_ascii())
     }
 
-    fn get_firmware_version(&mut self) -> Result<(u8, u8, u16, u16)> {
+    pub(crate) fn get_firmware_version(&mut self) -> Result<(u8, u8, u16, u16)> {
         debug!("Getting firmware version");
-        let response = self.send_command(b"V\x00", 6)?;
-        Ok((
-            response[0],
-            response[1],
-            u16::from_be_bytes([response[2], response[3]]),
-            u16::from_be_bytes([response[4], response[5]]),
-        ))
+        let response = self.send_command(&Command::GetFirmwareVersion)?;
+        Ok(response.as_firmware_version())
     }
 
     fn get_max_output_current(&mut self) -> Result<f64> {
         debug!("Getting maximum output current");
-        let response = self.send_command(b"CrMA\x00\x00", 2)?;
-        let max_current = i16::from_be_bytes([response[0], response[1]]) as f64 / 100.0;
+        let response = self.send_command(&Command::GetMaxOutputCurrent)?;
+        let max_current = response.as_i16() as f64 / 100.0;
         debug!("Maximum output current: {} mA", max_current);
         Ok(max_current)
     }
 
     pub fn get_temperature(&mut self) -> Result<f64> {
         debug!("Getting temperature");
-        let response = self.send_command(b"TCA", 2)?;
-        let temp = i16::from_be_bytes([response[0], response[1]]) as f64 * 0.0625;
+        let response = self.send_command(&Command::GetTemperature)?;
+        let temp = response.as_i16() as f64 * 0.0625;
         debug!("Temperature: {}°C", temp);
         Ok(temp)
     }
 
+    /// Read the configured `[lower, upper]` current limits in mA, from the
+    /// `CrLA` register.
+    pub fn get_current_limits(&mut self) -> Result<(f64, f64)> {
+        debug!("Getting current limits");
+        let response = self.send_command(&Command::GetCurrentLimits)?;
+        let (lower_raw, upper_raw) = response.as_i16_pair();
+        let (lower, upper) = (lower_raw as f64 / 100.0, upper_raw as f64 / 100.0);
+        debug!("Current limits: [{}, {}] mA", lower, upper);
+        Ok((lower, upper))
+    }
+
+    /// Write the `[lower, upper]` current limits in mA.
+    pub fn set_current_limits(&mut self, lower: f64, upper: f64) -> Result<()> {
+        info!("Setting current limits to [{}, {}] mA", lower, upper);
+        let command = Command::SetCurrentLimits {
+            lower_raw: (lower * 100.0) as i16,
+            upper_raw: (upper * 100.0) as i16,
+        };
+        self.send_command(&command).map(|_| ())
+    }
+
+    /// Read the configured `[lower, upper]` temperature limits in °C, from
+    /// the `TrLA` register.
+    pub fn get_temp_limits(&mut self) -> Result<(f64, f64)> {
+        debug!("Getting temperature limits");
+        let response = self.send_command(&Command::GetTempLimits)?;
+        let (lower_raw, upper_raw) = response.as_i16_pair();
+        let (lower, upper) = (lower_raw as f64 * 0.0625, upper_raw as f64 * 0.0625);
+        debug!("Temperature limits: [{}, {}]°C", lower, upper);
+        Ok((lower, upper))
+    }
+
+    /// Write the `[lower, upper]` temperature limits in °C.
+    pub fn set_temp_limits(&mut self, lower: f64, upper: f64) -> Result<()> {
+        info!("Setting temperature limits to [{}, {}]°C", lower, upper);
+        let command = Command::SetTempLimits {
+            lower_raw: (lower / 0.0625) as i16,
+            upper_raw: (upper / 0.0625) as i16,
+        };
+        self.send_command(&command).map(|_| ())
+    }
+
+    /// Reason the safety guard most recently tripped, if a fault is latched.
+    pub fn fault(&self) -> Option<&str> {
+        self.fault.as_deref()
+    }
+
+    /// Clear a latched safety fault, re-enabling `set_current`/`set_diopter`.
+    pub fn clear_fault(&mut self) {
+        self.fault = None;
+        self.event_log.record(crate::diagnostics::EventType::FaultCleared, "clear_fault");
+    }
+
+    pub(crate) fn latch_fault(&mut self, reason: String) {
+        self.event_log.record(crate::diagnostics::EventType::FaultRaised, reason.clone());
+        self.fault = Some(reason);
+    }
+
     pub fn set_mode(&mut self, mode: LensMode) -> Result<Option<(f64, f64)>> {
         info!("Setting mode to {:?}", mode);
+
+        // set_current/set_diopter share one slew limiter, so a mode switch
+        // must reseed it; otherwise the next call would compute a delta
+        // against the previous mode's units (mA vs. diopters) and emit a
+        // nonsense intermediate setpoint.
+        if let Some(limiter) = &mut self.slew {
+            limiter.last_value = None;
+            limiter.last_time = None;
+        }
+
         match mode {
             LensMode::Current => {
-                self.send_command(b"MwDA", 0)?;
+                self.send_command(&Command::SetModeCurrent)?;
                 self.mode = Some(LensMode::Current);
+                self.focal_power_range = None;
+                self.event_log.record(crate::diagnostics::EventType::ModeChange, format!("{:?}", mode));
                 Ok(None)
             }
             LensMode::FocalPower => {
-                let response = self.send_command(b"MwCA", 5)?;
+                let response = self.send_command(&Command::SetModeFocalPower)?;
                 self.mode = Some(LensMode::FocalPower);
-                
-                let min_fp_raw = i16::from_be_bytes([response[3], response[4]]) as f64 / 200.0;
-                let max_fp_raw = i16::from_be_bytes([response[1], response[2]]) as f64 / 200.0;
-                
+
+                let (min_fp_raw, max_fp_raw) = response.as_focal_power_range();
+                let min_fp_raw = min_fp_raw as f64 / 200.0;
+                let max_fp_raw = max_fp_raw as f64 / 200.0;
+
                 let (min_fp, max_fp) = if self.firmware_type == "A" {
                     (min_fp_raw - 5.0, max_fp_raw - 5.0)
                 } else {
                     (min_fp_raw, max_fp_raw)
                 };
-                
+
                 debug!("Focal power range: {} to {}", min_fp, max_fp);
+                self.focal_power_range = Some((min_fp, max_fp));
+                self.event_log.record(crate::diagnostics::EventType::ModeChange, format!("{:?}", mode));
                 Ok(Some((min_fp, max_fp)))
             }
         }
@@ -178,15 +459,15 @@ impl LensDriver {
 
     fn refresh_active_mode(&mut self) -> Result<()> {
         debug!("Refreshing active mode");
-        let response = self.send_command(b"MMA", 1)?;
-        self.mode = FromPrimitive::from_u8(response[0]);
+        let response = self.send_command(&Command::GetActiveMode)?;
+        self.mode = FromPrimitive::from_u8(response.as_u8());
         Ok(())
     }
 
     pub fn get_current(&mut self) -> Result<f64> {
         debug!("Getting current");
-        let response = self.send_command(b"Ar\x00\x00", 2)?;
-        let raw_current = i16::from_be_bytes([response[0], response[1]]) as f64;
+        let response = self.send_command(&Command::GetCurrent)?;
+        let raw_current = response.as_i16() as f64;
         let current = raw_current * self.max_output_current / 4095.0;
         debug!("Current: {} mA", current);
         Ok(current)
@@ -194,6 +475,9 @@ impl LensDriver {
 
     pub fn set_current(&mut self, current: f64) -> Result<()> {
         debug!("Setting current to {} mA", current);
+        if let Some(reason) = self.fault.clone() {
+            return Err(LensError::SafetyTripped { reason });
+        }
         if self.mode != Some(LensMode::Current) {
             return Err(LensError::WrongMode {
                 expected: LensMode::Current,
@@ -201,27 +485,79 @@ impl LensDriver {
             });
         }
 
-        let raw_current = (current * 4095.0 / self.max_output_current) as i16;
-        let mut cmd = Vec::from(&b"Aw"[..]);
-        cmd.extend_from_slice(&raw_current.to_be_bytes());
-        self.send_command(&cmd, 0).map(|_| ())
+        let current = self.apply_slew_limit(current);
+
+        let raw = (current * 4095.0 / self.max_output_current) as i16;
+        self.send_command(&Command::SetCurrent { raw }).map(|_| ())
     }
 
     pub fn get_diopter(&mut self) -> Result<f64> {
         debug!("Getting diopter");
-        let response = self.send_command(b"PrDA\x00\x00\x00\x00", 2)?;
-        let raw_diopter = i16::from_be_bytes([response[0], response[1]]) as f64;
+        let response = self.send_command(&Command::GetDiopter)?;
+        let raw_diopter = response.as_i16() as f64;
         let diopter = if self.firmware_type == "A" {
             raw_diopter / 200.0 - 5.0
         } else {
             raw_diopter / 200.0
         };
+
+        let diopter = match self.temp_compensation {
+            Some(comp) => {
+                let temperature = self.get_temperature()?;
+                diopter + comp.k * (temperature - comp.t_ref)
+            }
+            None => diopter,
+        };
+
         debug!("Diopter: {}", diopter);
         Ok(diopter)
     }
 
+    /// Enable temperature-compensated focal power: `set_diopter` will shift
+    /// its raw setpoint by `k` diopters per °C of deviation from `t_ref` to
+    /// counteract the lens's passive thermal drift.
+    pub fn set_temp_compensation(&mut self, t_ref: f64, k: f64) {
+        info!("Enabling temperature compensation: t_ref={}°C, k={} D/°C", t_ref, k);
+        self.temp_compensation = Some(TempCompensation { t_ref, k });
+    }
+
+    /// Disable temperature compensation; `set_diopter`/`get_diopter` go back
+    /// to using the raw commanded/reported value.
+    pub fn disable_temp_compensation(&mut self) {
+        self.temp_compensation = None;
+    }
+
+    /// Cap the rate of change of `set_current`/`set_diopter` to `rate` per
+    /// second (mA/s or diopter/s, depending on the active mode), protecting
+    /// the lens against accidental full-scale commands. `ramp_to_zero`
+    /// bypasses this limit since it already steps at its own pace.
+    pub fn set_slew_rate(&mut self, rate: f64) {
+        info!("Enabling slew-rate limit: {} per second", rate);
+        self.slew = Some(SlewLimiter {
+            rate,
+            last_value: None,
+            last_time: None,
+        });
+    }
+
+    /// Disable the slew-rate limit; `set_current`/`set_diopter` jump
+    /// directly to the commanded value again.
+    pub fn disable_slew(&mut self) {
+        self.slew = None;
+    }
+
+    fn apply_slew_limit(&mut self, target: f64) -> f64 {
+        match &mut self.slew {
+            Some(limiter) => limiter.apply(target, Instant::now()),
+            None => target,
+        }
+    }
+
     pub fn set_diopter(&mut self, diopter: f64) -> Result<()> {
         debug!("Setting diopter to {}", diopter);
+        if let Some(reason) = self.fault.clone() {
+            return Err(LensError::SafetyTripped { reason });
+        }
         if self.mode != Some(LensMode::FocalPower) {
             return Err(LensError::WrongMode {
                 expected: LensMode::FocalPower,
@@ -229,16 +565,106 @@ impl LensDriver {
             });
         }
 
-        let raw_diopter = if self.firmware_type == "A" {
-            ((diopter + 5.0) * 200.0) as i16
+        self.last_logical_diopter = Some(diopter);
+        let diopter = self.apply_slew_limit(diopter);
+
+        let corrected = match self.temp_compensation {
+            Some(comp) => {
+                let temperature = self.get_temperature()?;
+                diopter - comp.k * (temperature - comp.t_ref)
+            }
+            None => diopter,
+        };
+
+        let raw = if self.firmware_type == "A" {
+            ((corrected + 5.0) * 200.0) as i16
         } else {
-            (diopter * 200.0) as i16
+            (corrected * 200.0) as i16
+        };
+
+        self.send_command(&Command::SetDiopter { raw }).map(|_| ())
+    }
+
+    /// Capture a full snapshot of the device's state in one shot.
+    pub fn status_report(&mut self) -> Result<LensStatus> {
+        debug!("Building status report");
+        let temperature = self.get_temperature()?;
+        let current_ma = self.get_current()?;
+        let diopter = match self.mode {
+            Some(LensMode::FocalPower) => Some(self.get_diopter()?),
+            _ => None,
         };
 
-        let mut cmd = Vec::from(&b"PwDA"[..]);
-        cmd.extend_from_slice(&raw_diopter.to_be_bytes());
-        cmd.extend_from_slice(&[0, 0]);
-        self.send_command(&cmd, 0).map(|_| ())
+        Ok(LensStatus {
+            mode: self.mode,
+            temperature,
+            current_ma,
+            diopter,
+            max_output_current: self.max_output_current,
+            firmware_type: self.firmware_type.clone(),
+            firmware_version: self.firmware_version,
+            focal_power_range: self.focal_power_range,
+        })
+    }
+
+    /// Repeatedly build a [`LensStatus`] snapshot on a background thread,
+    /// forwarding each one to `callback`. Consumes the driver since the
+    /// polling loop owns it for as long as it runs.
+    pub fn poll_status<F>(mut self, interval: Duration, mut callback: F) -> thread::JoinHandle<()>
+    where
+        F: FnMut(LensStatus) + Send + 'static,
+    {
+        thread::spawn(move || loop {
+            match self.status_report() {
+                Ok(status) => callback(status),
+                Err(e) => error!("Error building status report: {}", e),
+            }
+            thread::sleep(interval);
+        })
+    }
+
+    /// Spawn a background thread that watches for temperature drift and
+    /// re-applies the most recent logical diopter target whenever the
+    /// temperature has moved by more than `threshold` °C since the last
+    /// correction, keeping effective focal power constant during warm-up.
+    ///
+    /// Takes `Arc<Mutex<LensDriver>>` rather than `&mut self` because the
+    /// correction thread needs to call back into `set_diopter` on its own
+    /// schedule while the caller keeps issuing foreground commands.
+    pub fn spawn_temp_correction(
+        driver: Arc<Mutex<LensDriver>>,
+        threshold: f64,
+        interval: Duration,
+    ) -> thread::JoinHandle<()> {
+        thread::spawn(move || {
+            let mut last_temperature: Option<f64> = None;
+
+            loop {
+                thread::sleep(interval);
+
+                let mut driver = driver.lock().unwrap();
+                let temperature = match driver.get_temperature() {
+                    Ok(t) => t,
+                    Err(e) => {
+                        error!("Error reading temperature for correction: {}", e);
+                        continue;
+                    }
+                };
+
+                let drifted = last_temperature
+                    .map(|t| (temperature - t).abs() > threshold)
+                    .unwrap_or(true);
+
+                if drifted {
+                    if let Some(target) = driver.last_logical_diopter {
+                        if let Err(e) = driver.set_diopter(target) {
+                            error!("Error re-applying temperature-corrected diopter: {}", e);
+                        }
+                    }
+                    last_temperature = Some(temperature);
+                }
+            }
+        })
     }
 
     pub fn ramp_to_zero(&mut self, duration: f64, steps: usize) -> Result<()> {
@@ -253,7 +679,13 @@ impl LensDriver {
             }
         };
 
-        self.ramp(start_value, 0.0, duration, steps, set_func)?;
+        // ramp() already steps toward the target at its own pace, so bypass
+        // any configured slew limit rather than compounding the two.
+        let saved_slew = self.slew.take();
+        let result = self.ramp(start_value, 0.0, duration, steps, set_func);
+        self.slew = saved_slew;
+        result?;
+
         info!("Ramp to zero complete");
         Ok(())
     }
@@ -271,16 +703,18 @@ impl LensDriver {
         Ok(())
     }
 
-    fn send_command(&mut self, command: &[u8], reply_size: usize) -> Result<Vec<u8>> {
-        let crc = self.calculate_crc_16(command);
-        let mut cmd_with_crc = Vec::from(command);
+    pub(crate) fn send_command(&mut self, command: &Command) -> Result<Response> {
+        let payload = command.payload();
+        let crc = self.calculate_crc_16(&payload);
+        let mut cmd_with_crc = payload;
         cmd_with_crc.extend_from_slice(&crc.to_le_bytes());
 
         debug!("Sending command: {:?}", cmd_with_crc);
         self.port.write_all(&cmd_with_crc)?;
 
+        let reply_size = command.reply_len();
         if reply_size == 0 {
-            return Ok(Vec::new());
+            return Ok(Response::new(Vec::new()));
         }
 
         let mut response = vec![0u8; reply_size + 4];
@@ -288,12 +722,14 @@ impl LensDriver {
 
         let (data, rest) = response.split_at(reply_size);
         let crc_received = u16::from_le_bytes([rest[0], rest[1]]);
-        
+
         if crc_received != self.calculate_crc_16(data) || &rest[2..4] != b"\r\n" {
+            self.crc_failures += 1;
+            self.event_log.record(crate::diagnostics::EventType::CrcFailure, "send_command");
             return Err(LensError::CrcError);
         }
 
-        Ok(data.to_vec())
+        Ok(Response::new(data.to_vec()))
     }
 
     fn calculate_crc_16(&self, data: &[u8]) -> u16 {
@@ -319,4 +755,44 @@ impl Drop for LensDriver {
             error!("Error while ramping to zero during drop: {}", e);
         }
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unseeded_limiter_passes_target_through() {
+        let mut limiter = SlewLimiter { rate: 10.0, last_value: None, last_time: None };
+        let now = Instant::now();
+        assert_eq!(limiter.apply(5.0, now), 5.0);
+    }
+
+    #[test]
+    fn limiter_caps_step_to_rate_times_elapsed() {
+        let mut limiter = SlewLimiter { rate: 10.0, last_value: None, last_time: None };
+        let t0 = Instant::now();
+        limiter.apply(0.0, t0);
+
+        let t1 = t0 + Duration::from_millis(500);
+        let limited = limiter.apply(100.0, t1);
+        assert!((limited - 5.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn reseeding_after_mode_switch_passes_new_target_through_unlimited() {
+        // Simulates set_mode(): a current-mode value (mA) is already seeded,
+        // then the mode switches to focal power (diopters). Without a
+        // reseed, the next apply() would compute a delta between the two
+        // units and emit a nonsense intermediate value.
+        let mut limiter = SlewLimiter { rate: 10.0, last_value: None, last_time: None };
+        let t0 = Instant::now();
+        limiter.apply(200.0, t0); // e.g. 200 mA in current mode
+
+        limiter.last_value = None;
+        limiter.last_time = None;
+
+        let t1 = t0 + Duration::from_millis(10);
+        let diopter_target = 2.5;
+        assert_eq!(limiter.apply(diopter_target, t1), diopter_target);
+    }
+}
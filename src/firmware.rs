@@ -0,0 +1,79 @@
+use log::{debug, info};
+
+use crate::command::Command;
+use crate::driver::{LensDriver, LensError, Result};
+
+/// Bootloader-reported state of a pending firmware update, mirroring the
+/// `Idle`/`Swapped`/`Verifying` states of an MCU dual-bank bootloader.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BootState {
+    Idle,
+    Swapped,
+    Verifying,
+}
+
+const FLASH_BLOCK_SIZE: usize = 256;
+const FLASH_MAX_RETRIES: usize = 3;
+
+impl LensDriver {
+    /// Issue the vendor bootloader-entry sequence and re-handshake in DFU
+    /// mode, so subsequent `flash_firmware` calls reach the bootloader
+    /// rather than the application firmware.
+    pub fn enter_bootloader(&mut self) -> Result<()> {
+        info!("Entering bootloader");
+        self.send_command(&Command::EnterBootloader)?;
+        self.raw_handshake(b"Boot\r\n")
+    }
+
+    /// Flash `image` into the device, chunked into CRC-framed blocks over
+    /// the existing `send_command` path, retrying each block on failure.
+    pub fn flash_firmware(&mut self, image: &[u8]) -> Result<()> {
+        info!("Flashing firmware image ({} bytes)", image.len());
+
+        for (index, block) in image.chunks(FLASH_BLOCK_SIZE).enumerate() {
+            let command = Command::FlashBlock {
+                index: index as u16,
+                data: block.to_vec(),
+            };
+
+            let mut attempt = 0;
+            loop {
+                match self.send_command(&command) {
+                    Ok(response) if response.as_u8() == 0x06 => break,
+                    Ok(_) if attempt < FLASH_MAX_RETRIES => {
+                        attempt += 1;
+                        debug!("Block {} not acknowledged, retrying (attempt {})", index, attempt);
+                    }
+                    Ok(_) => return Err(LensError::CrcError),
+                    Err(e) if attempt < FLASH_MAX_RETRIES => {
+                        attempt += 1;
+                        debug!("Block {} failed ({}), retrying (attempt {})", index, e, attempt);
+                    }
+                    Err(e) => return Err(e),
+                }
+            }
+        }
+
+        info!("Firmware image flashed successfully");
+        Ok(())
+    }
+
+    /// Read back the bootloader's current state, so callers can run a
+    /// self-test before committing to the new image.
+    pub fn get_bootloader_state(&mut self) -> Result<BootState> {
+        debug!("Getting bootloader state");
+        let response = self.send_command(&Command::GetBootloaderState)?;
+        match response.as_u8() {
+            0 => Ok(BootState::Idle),
+            1 => Ok(BootState::Swapped),
+            2 => Ok(BootState::Verifying),
+            _ => Err(LensError::InvalidMode),
+        }
+    }
+
+    /// Verify the running firmware version matches `expected` after reboot.
+    pub fn verify_firmware(&mut self, expected: (u8, u8, u16, u16)) -> Result<bool> {
+        let version = self.get_firmware_version()?;
+        Ok(version == expected)
+    }
+}
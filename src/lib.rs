@@ -1,13 +1,34 @@
 use pyo3::prelude::*;
+use pyo3::wrap_pyfunction;
 
+mod calibration;
+mod command;
+mod diagnostics;
 mod driver;
+mod firmware;
 mod python;
+mod safety;
+mod telemetry;
+mod waveform;
 
+pub use calibration::{CalibrationModel, CalibrationSample};
+pub use diagnostics::{EventLogEntry, EventType, FaultFlags, FaultReport};
 pub use driver::*;
+pub use firmware::BootState;
+pub use safety::SafetyGuard;
+pub use telemetry::{TelemetryMonitor, TelemetrySample};
+pub use waveform::{WaveformConfig, WaveformShape};
 
 /// Entry point for the Python module
 #[pymodule]
 fn lens_driver(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<python::PyLensDriver>()?;
+    m.add_class::<python::PyLensInfo>()?;
+    m.add_class::<python::PyWaveformShape>()?;
+    m.add_class::<python::PyWaveformConfig>()?;
+    m.add_class::<python::PyCalibrationSample>()?;
+    m.add_class::<python::PyFaultReport>()?;
+    m.add_class::<python::PyEventLogEntry>()?;
+    m.add_function(wrap_pyfunction!(python::list_lens_drivers, m)?)?;
     Ok(())
 }
\ No newline at end of file
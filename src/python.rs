@@ -1,12 +1,221 @@
 use pyo3::prelude::*;
 use pyo3::exceptions;
 use log::error;
+use std::sync::{Arc, Mutex};
 
-use crate::driver::{LensDriver as RustLensDriver, LensMode, LensError};
+use crate::calibration::CalibrationSample;
+use crate::diagnostics::{EventLogEntry, EventType, FaultReport};
+use crate::driver::{LensDriver as RustLensDriver, LensInfo, LensMode, LensError};
+use crate::telemetry::TelemetryMonitor;
+use crate::waveform::{WaveformConfig, WaveformGenerator, WaveformShape};
+
+/// A lens controller discovered on a serial port, returned by
+/// `list_lens_drivers()` so callers can pick a device by firmware
+/// attributes instead of hard-coding a path.
+#[pyclass]
+#[derive(Clone)]
+pub struct PyLensInfo {
+    #[pyo3(get)]
+    port_name: String,
+    #[pyo3(get)]
+    firmware_type: String,
+    #[pyo3(get)]
+    firmware_version: (u8, u8, u16, u16),
+    #[pyo3(get)]
+    max_output_current: f64,
+}
+
+#[pymethods]
+impl PyLensInfo {
+    fn __repr__(&self) -> String {
+        format!(
+            "PyLensInfo(port_name={:?}, firmware_type={:?})",
+            self.port_name, self.firmware_type
+        )
+    }
+}
+
+impl From<LensInfo> for PyLensInfo {
+    fn from(info: LensInfo) -> Self {
+        PyLensInfo {
+            port_name: info.port_name,
+            firmware_type: info.firmware_type,
+            firmware_version: info.firmware_version,
+            max_output_current: info.max_output_current,
+        }
+    }
+}
+
+/// Enumerate all serial ports, attempt the device handshake on each, and
+/// return a record for every lens controller found.
+#[pyfunction]
+pub fn list_lens_drivers() -> PyResult<Vec<PyLensInfo>> {
+    RustLensDriver::enumerate()
+        .map(|found| found.into_iter().map(PyLensInfo::from).collect())
+        .map_err(|e| PyErr::new::<exceptions::PyRuntimeError, _>(e.to_string()))
+}
+
+/// Shape of a periodic current/focal-power sweep driven by `start_waveform`.
+#[pyclass(name = "WaveformShape")]
+#[derive(Clone, Copy)]
+pub enum PyWaveformShape {
+    Sine,
+    Triangle,
+    Rectangular,
+    Sawtooth,
+}
+
+impl From<PyWaveformShape> for WaveformShape {
+    fn from(shape: PyWaveformShape) -> Self {
+        match shape {
+            PyWaveformShape::Sine => WaveformShape::Sine,
+            PyWaveformShape::Triangle => WaveformShape::Triangle,
+            PyWaveformShape::Rectangular => WaveformShape::Rectangular,
+            PyWaveformShape::Sawtooth => WaveformShape::Sawtooth,
+        }
+    }
+}
+
+/// Configuration for a periodic current/focal-power sweep: frequency (Hz),
+/// amplitude, DC offset, and duty cycle (only used for `Rectangular`).
+#[pyclass(name = "WaveformConfig")]
+#[derive(Clone)]
+pub struct PyWaveformConfig {
+    shape: PyWaveformShape,
+    frequency_hz: f64,
+    amplitude: f64,
+    offset: f64,
+    duty_cycle: f64,
+}
+
+#[pymethods]
+impl PyWaveformConfig {
+    #[new]
+    #[pyo3(signature = (shape, frequency_hz, amplitude, offset=0.0, duty_cycle=0.5))]
+    fn new(shape: PyWaveformShape, frequency_hz: f64, amplitude: f64, offset: f64, duty_cycle: f64) -> Self {
+        PyWaveformConfig {
+            shape,
+            frequency_hz,
+            amplitude,
+            offset,
+            duty_cycle,
+        }
+    }
+}
+
+impl From<PyWaveformConfig> for WaveformConfig {
+    fn from(config: PyWaveformConfig) -> Self {
+        WaveformConfig {
+            shape: config.shape.into(),
+            frequency_hz: config.frequency_hz,
+            amplitude: config.amplitude,
+            offset: config.offset,
+            duty_cycle: config.duty_cycle,
+        }
+    }
+}
+
+/// One calibration measurement: the current commanded, the lens temperature
+/// at measurement time, and the focal power actually observed downstream.
+#[pyclass(name = "CalibrationSample")]
+#[derive(Clone, Copy)]
+pub struct PyCalibrationSample {
+    current_ma: f64,
+    temperature_c: f64,
+    diopter: f64,
+}
+
+#[pymethods]
+impl PyCalibrationSample {
+    #[new]
+    fn new(current_ma: f64, temperature_c: f64, diopter: f64) -> Self {
+        PyCalibrationSample {
+            current_ma,
+            temperature_c,
+            diopter,
+        }
+    }
+}
+
+impl From<PyCalibrationSample> for CalibrationSample {
+    fn from(sample: PyCalibrationSample) -> Self {
+        CalibrationSample {
+            current_ma: sample.current_ma,
+            temperature_c: sample.temperature_c,
+            diopter: sample.diopter,
+        }
+    }
+}
+
+/// Decoded device fault flags plus the driver's own handshake/CRC failure
+/// counters, turning opaque runtime errors into actionable diagnostics.
+#[pyclass(name = "FaultReport")]
+#[derive(Clone)]
+pub struct PyFaultReport {
+    #[pyo3(get)]
+    over_temperature: bool,
+    #[pyo3(get)]
+    over_current: bool,
+    #[pyo3(get)]
+    undervoltage: bool,
+    #[pyo3(get)]
+    handshake_failures: u64,
+    #[pyo3(get)]
+    crc_failures: u64,
+    #[pyo3(get)]
+    descriptions: Vec<String>,
+}
+
+impl From<FaultReport> for PyFaultReport {
+    fn from(report: FaultReport) -> Self {
+        PyFaultReport {
+            over_temperature: report.flags.over_temperature,
+            over_current: report.flags.over_current,
+            undervoltage: report.flags.undervoltage,
+            handshake_failures: report.handshake_failures,
+            crc_failures: report.crc_failures,
+            descriptions: report.flags.descriptions().into_iter().map(String::from).collect(),
+        }
+    }
+}
+
+/// One entry in the driver's in-memory event log.
+#[pyclass(name = "EventLogEntry")]
+#[derive(Clone)]
+pub struct PyEventLogEntry {
+    #[pyo3(get)]
+    timestamp: f64,
+    #[pyo3(get)]
+    event_type: String,
+    #[pyo3(get)]
+    source: String,
+    #[pyo3(get)]
+    run_number: u64,
+}
+
+impl From<EventLogEntry> for PyEventLogEntry {
+    fn from(entry: EventLogEntry) -> Self {
+        let event_type = match entry.event_type {
+            EventType::ModeChange => "mode_change",
+            EventType::CrcFailure => "crc_failure",
+            EventType::FaultRaised => "fault_raised",
+            EventType::FaultCleared => "fault_cleared",
+        };
+
+        PyEventLogEntry {
+            timestamp: entry.timestamp,
+            event_type: event_type.to_string(),
+            source: entry.source,
+            run_number: entry.run_number,
+        }
+    }
+}
 
 #[pyclass]
 pub struct PyLensDriver {
-    inner: RustLensDriver,
+    inner: Arc<Mutex<RustLensDriver>>,
+    waveform: Option<WaveformGenerator>,
+    monitor: Option<TelemetryMonitor>,
 }
 
 #[pymethods]
@@ -14,12 +223,16 @@ impl PyLensDriver {
     #[new]
     fn new(port_name: &str, debug: bool) -> PyResult<Self> {
         RustLensDriver::new(port_name, debug)
-            .map(|inner| PyLensDriver { inner })
+            .map(|inner| PyLensDriver {
+                inner: Arc::new(Mutex::new(inner)),
+                waveform: None,
+                monitor: None,
+            })
             .map_err(|e| PyErr::new::<exceptions::PyRuntimeError, _>(e.to_string()))
     }
 
     fn get_mode(&self) -> PyResult<String> {
-        match self.inner.mode() {
+        match self.inner.lock().unwrap().mode() {
             Some(LensMode::Current) => Ok("current".to_string()),
             Some(LensMode::FocalPower) => Ok("focal_power".to_string()),
             None => Ok("unknown".to_string()),
@@ -29,15 +242,17 @@ impl PyLensDriver {
     /// Get the current temperature of the lens
     fn get_temperature(&mut self) -> PyResult<f64> {
         self.inner
+            .lock()
+            .unwrap()
             .get_temperature()
             .map_err(|e| PyErr::new::<exceptions::PyRuntimeError, _>(e.to_string()))
     }
 
     /// Set the operation mode of the lens
-    /// 
+    ///
     /// Args:
     ///     mode (str): Either "current" or "focal_power"
-    /// 
+    ///
     /// Returns:
     ///     Optional tuple of (min_fp, max_fp) when setting focal_power mode
     fn set_mode(&mut self, mode: &str) -> PyResult<Option<(f64, f64)>> {
@@ -50,6 +265,8 @@ impl PyLensDriver {
         };
 
         self.inner
+            .lock()
+            .unwrap()
             .set_mode(lens_mode)
             .map_err(|e| PyErr::new::<exceptions::PyRuntimeError, _>(e.to_string()))
     }
@@ -58,6 +275,8 @@ impl PyLensDriver {
     /// Get the current in mA
     fn get_current(&mut self) -> PyResult<f64> {
         self.inner
+            .lock()
+            .unwrap()
             .get_current()
             .map_err(|e| PyErr::new::<exceptions::PyRuntimeError, _>(e.to_string()))
     }
@@ -65,6 +284,8 @@ impl PyLensDriver {
     /// Set the current in mA
     fn set_current(&mut self, current: f64) -> PyResult<()> {
         self.inner
+            .lock()
+            .unwrap()
             .set_current(current)
             .map_err(|e| PyErr::new::<exceptions::PyRuntimeError, _>(e.to_string()))
     }
@@ -72,6 +293,8 @@ impl PyLensDriver {
     /// Get the focal power in diopters
     fn get_diopter(&mut self) -> PyResult<f64> {
         self.inner
+            .lock()
+            .unwrap()
             .get_diopter()
             .map_err(|e| PyErr::new::<exceptions::PyRuntimeError, _>(e.to_string()))
     }
@@ -79,35 +302,168 @@ impl PyLensDriver {
     /// Set the focal power in diopters
     fn set_diopter(&mut self, diopter: f64) -> PyResult<()> {
         self.inner
+            .lock()
+            .unwrap()
             .set_diopter(diopter)
             .map_err(|e| PyErr::new::<exceptions::PyRuntimeError, _>(e.to_string()))
     }
 
+    /// Fit a temperature-drift calibration model from `samples` and store it
+    /// for use by `set_diopter_compensated`.
+    fn calibrate(&mut self, samples: Vec<PyCalibrationSample>) -> PyResult<()> {
+        let samples: Vec<CalibrationSample> = samples.into_iter().map(Into::into).collect();
+        self.inner
+            .lock()
+            .unwrap()
+            .calibrate(&samples)
+            .map_err(|e| PyErr::new::<exceptions::PyRuntimeError, _>(e.to_string()))
+    }
+
+    /// Load a previously saved calibration model from `path`.
+    fn load_calibration(&mut self, path: &str) -> PyResult<()> {
+        self.inner
+            .lock()
+            .unwrap()
+            .load_calibration(path)
+            .map_err(|e| PyErr::new::<exceptions::PyRuntimeError, _>(e.to_string()))
+    }
+
+    /// Save the currently fit calibration model to `path` as JSON.
+    fn save_calibration(&self, path: &str) -> PyResult<()> {
+        self.inner
+            .lock()
+            .unwrap()
+            .save_calibration(path)
+            .map_err(|e| PyErr::new::<exceptions::PyRuntimeError, _>(e.to_string()))
+    }
+
+    /// Set the focal power in diopters, compensating for the current lens
+    /// temperature using the stored calibration model.
+    fn set_diopter_compensated(&mut self, target: f64) -> PyResult<()> {
+        self.inner
+            .lock()
+            .unwrap()
+            .set_diopter_compensated(target)
+            .map_err(|e| PyErr::new::<exceptions::PyRuntimeError, _>(e.to_string()))
+    }
+
+    /// Cap the rate of change of `set_current`/`set_diopter` to `rate` per
+    /// second (mA/s or diopter/s, depending on the active mode).
+    fn set_slew_rate(&mut self, rate: f64) {
+        self.inner.lock().unwrap().set_slew_rate(rate);
+    }
+
+    /// Disable the slew-rate limit; setpoints jump directly to the
+    /// commanded value again.
+    fn disable_slew(&mut self) {
+        self.inner.lock().unwrap().disable_slew();
+    }
+
     /// Ramp the lens setting to zero over a specified duration
-    /// 
+    ///
     /// Args:
     ///     duration (float): Time in seconds over which to ramp
     ///     steps (int): Number of steps to use in the ramp
     fn ramp_to_zero(&mut self, duration: f64, steps: usize) -> PyResult<()> {
         self.inner
+            .lock()
+            .unwrap()
             .ramp_to_zero(duration, steps)
             .map_err(|e| PyErr::new::<exceptions::PyRuntimeError, _>(e.to_string()))
     }
 
+    /// Start driving the active mode's setpoint as a continuous periodic
+    /// waveform (sine/triangle/rectangular/sawtooth), useful for focus
+    /// sweeps and z-stack scanning. Replaces any waveform already running.
+    fn start_waveform(&mut self, config: PyWaveformConfig) {
+        if let Some(running) = self.waveform.take() {
+            running.stop(self.inner.clone());
+        }
+        self.waveform = Some(RustLensDriver::start_waveform(self.inner.clone(), config.into()));
+    }
+
+    /// Stop any running waveform and ramp the output back to zero.
+    fn stop_waveform(&mut self) {
+        if let Some(running) = self.waveform.take() {
+            running.stop(self.inner.clone());
+        }
+    }
+
+    /// Start sampling temperature and current into a fixed-capacity ring
+    /// buffer at `rate_hz`, for correlating lens state with an experiment
+    /// timeline without polling in a hot loop. Replaces any monitor already
+    /// running.
+    fn start_monitoring(&mut self, rate_hz: f64, capacity: usize) {
+        if let Some(running) = self.monitor.take() {
+            running.stop();
+        }
+        self.monitor = Some(RustLensDriver::start_monitoring(self.inner.clone(), rate_hz, capacity));
+    }
+
+    /// Stop the background telemetry sampler, if one is running.
+    fn stop_monitoring(&mut self) {
+        if let Some(running) = self.monitor.take() {
+            running.stop();
+        }
+    }
+
+    /// Fetch the buffered telemetry samples as `(timestamps, temperatures,
+    /// currents_ma)`, ready to hand to `numpy.array()` on the Python side.
+    fn get_telemetry(&self) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+        let samples = match &self.monitor {
+            Some(monitor) => monitor.samples(),
+            None => Vec::new(),
+        };
+
+        let mut timestamps = Vec::with_capacity(samples.len());
+        let mut temperatures = Vec::with_capacity(samples.len());
+        let mut currents_ma = Vec::with_capacity(samples.len());
+        for sample in samples {
+            timestamps.push(sample.timestamp);
+            temperatures.push(sample.temperature);
+            currents_ma.push(sample.current_ma);
+        }
+
+        (timestamps, temperatures, currents_ma)
+    }
+
+    /// Read and decode the controller's status/fault register, combined
+    /// with the driver's own handshake/CRC failure counters.
+    fn fault_report(&mut self) -> PyResult<PyFaultReport> {
+        self.inner
+            .lock()
+            .unwrap()
+            .fault_report()
+            .map(Into::into)
+            .map_err(|e| PyErr::new::<exceptions::PyRuntimeError, _>(e.to_string()))
+    }
+
+    /// Snapshot of the mode-change/CRC-failure/fault-transition event log
+    /// recorded so far for this driver session.
+    fn get_event_log(&self) -> Vec<PyEventLogEntry> {
+        self.inner
+            .lock()
+            .unwrap()
+            .get_event_log()
+            .iter()
+            .cloned()
+            .map(Into::into)
+            .collect()
+    }
 
     #[getter]
     fn firmware_type(&self) -> String {
-        self.inner.firmware_type().to_string()
+        self.inner.lock().unwrap().firmware_type().to_string()
     }
 
     #[getter]
     fn firmware_version(&self) -> (u8, u8, u16, u16) {
-        self.inner.firmware_version()
+        self.inner.lock().unwrap().firmware_version()
     }
 
     #[getter]
     fn max_output_current(&self) -> f64 {
-        self.inner.max_output_current()
+        self.inner.lock().unwrap().max_output_current()
     }
 
     fn __repr__(&self) -> PyResult<String> {
@@ -130,18 +486,15 @@ impl PyLensDriver {
         exc_value: Option<PyObject>,
         traceback: Option<PyObject>,
     ) {
-        if let Err(e) = self.inner.ramp_to_zero(1.0, 50) {
+        self.stop_waveform();
+        self.stop_monitoring();
+        if let Err(e) = self.inner.lock().unwrap().ramp_to_zero(1.0, 50) {
             error!("Error during context manager exit: {}", e);
         }
     }
 }
 
 
-// Helper function to convert Rust errors to Python exceptions
-fn to_py_err<E: std::error::Error>(err: E) -> PyErr {
-    PyErr::new::<exceptions::PyRuntimeError, _>(err.to_string())
-}
-
 // Function to create better Python error messages from Rust errors
 impl From<LensError> for PyErr {
     fn from(err: LensError) -> PyErr {
@@ -166,6 +519,15 @@ impl From<LensError> for PyErr {
             LensError::Io(e) => {
                 PyErr::new::<exceptions::PyIOError, _>(format!("IO error: {}", e))
             }
+            LensError::SafetyTripped { reason } => {
+                PyErr::new::<exceptions::PyRuntimeError, _>(format!("Safety interlock tripped: {}", reason))
+            }
+            LensError::Json(e) => {
+                PyErr::new::<exceptions::PyValueError, _>(format!("Calibration JSON error: {}", e))
+            }
+            LensError::Calibration(reason) => {
+                PyErr::new::<exceptions::PyRuntimeError, _>(format!("Calibration error: {}", reason))
+            }
         }
     }
 }
\ No newline at end of file
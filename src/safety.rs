@@ -0,0 +1,106 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use log::error;
+
+use crate::driver::LensDriver;
+
+/// Active background protector for the lens coil, inspired by the
+/// current/temperature excursion protectors found on laser-diode drivers.
+///
+/// While running, it polls [`LensDriver::get_current`] and
+/// [`LensDriver::get_temperature`] at `interval`. If the current strays
+/// outside the configured `[lower, upper]` band, or the temperature exceeds
+/// the configured upper limit, for longer than `debounce`, it ramps the
+/// output to zero and latches a fault on the driver so subsequent
+/// `set_current`/`set_diopter` calls return [`crate::LensError::SafetyTripped`]
+/// until [`LensDriver::clear_fault`] is called.
+pub struct SafetyGuard {
+    stop: Arc<AtomicBool>,
+    handle: Option<thread::JoinHandle<()>>,
+}
+
+impl SafetyGuard {
+    /// Spawn the guard on a background thread. It polls and, on a tripped
+    /// fault, writes back to the same driver the caller is issuing foreground
+    /// `set_current`/`set_diopter` calls on, so both sides need a shared,
+    /// lockable handle rather than ownership.
+    pub fn spawn(driver: Arc<Mutex<LensDriver>>, interval: Duration, debounce: Duration) -> Self {
+        let stop = Arc::new(AtomicBool::new(false));
+        let stop_for_thread = stop.clone();
+
+        let handle = thread::spawn(move || {
+            let mut out_of_band_since: Option<Instant> = None;
+
+            while !stop_for_thread.load(Ordering::Relaxed) {
+                thread::sleep(interval);
+
+                let mut lens = driver.lock().unwrap();
+
+                let (current, temperature, current_limits, temp_limits) = match (
+                    lens.get_current(),
+                    lens.get_temperature(),
+                    lens.get_current_limits(),
+                    lens.get_temp_limits(),
+                ) {
+                    (Ok(c), Ok(t), Ok(cl), Ok(tl)) => (c, t, cl, tl),
+                    _ => continue,
+                };
+
+                let (current_lower, current_upper) = current_limits;
+                let (_, temp_upper) = temp_limits;
+
+                let tripped = current < current_lower
+                    || current > current_upper
+                    || temperature > temp_upper;
+
+                if !tripped {
+                    out_of_band_since = None;
+                    continue;
+                }
+
+                let since = *out_of_band_since.get_or_insert_with(Instant::now);
+                if since.elapsed() < debounce {
+                    continue;
+                }
+
+                let reason = format!(
+                    "current {:.2} mA outside [{:.2}, {:.2}] or temperature {:.2}°C above {:.2}°C",
+                    current, current_lower, current_upper, temperature, temp_upper
+                );
+                error!("Safety guard tripped: {}", reason);
+
+                if let Err(e) = lens.ramp_to_zero(0.5, 10) {
+                    error!("Error ramping to zero after safety trip: {}", e);
+                }
+                lens.latch_fault(reason);
+                out_of_band_since = None;
+            }
+        });
+
+        SafetyGuard {
+            stop,
+            handle: Some(handle),
+        }
+    }
+
+    /// Stop polling and wait for the background thread to exit. Does not
+    /// clear any fault that has already been latched.
+    pub fn stop(mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for SafetyGuard {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
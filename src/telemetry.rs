@@ -0,0 +1,120 @@
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use log::error;
+
+use crate::driver::LensDriver;
+
+/// One telemetry sample: wall-clock timestamp (Unix epoch, seconds) paired
+/// with the temperature and current read at that instant.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TelemetrySample {
+    pub timestamp: f64,
+    pub temperature: f64,
+    pub current_ma: f64,
+}
+
+/// A running background sampler, polling temperature and current into a
+/// fixed-capacity ring buffer at a fixed rate so callers can correlate lens
+/// state with their experiment timeline without polling in a hot loop.
+pub struct TelemetryMonitor {
+    stop: Arc<AtomicBool>,
+    handle: Option<thread::JoinHandle<()>>,
+    buffer: Arc<Mutex<VecDeque<TelemetrySample>>>,
+}
+
+impl TelemetryMonitor {
+    /// Start sampling `driver` at `rate_hz`, keeping only the most recent
+    /// `capacity` samples (oldest entries are overwritten once full). A
+    /// `capacity` of 0 discards every sample instead of growing unbounded.
+    pub fn start(driver: Arc<Mutex<LensDriver>>, rate_hz: f64, capacity: usize) -> Self {
+        let stop = Arc::new(AtomicBool::new(false));
+        let stop_for_thread = stop.clone();
+        let buffer = Arc::new(Mutex::new(VecDeque::with_capacity(capacity)));
+        let buffer_for_thread = buffer.clone();
+        let period = Duration::from_secs_f64(1.0 / rate_hz);
+
+        let handle = thread::spawn(move || {
+            while !stop_for_thread.load(Ordering::Relaxed) {
+                let tick_start = Instant::now();
+
+                let mut lens = driver.lock().unwrap();
+                let sample = match (lens.get_temperature(), lens.get_current()) {
+                    (Ok(temperature), Ok(current_ma)) => Some(TelemetrySample {
+                        timestamp: unix_time(),
+                        temperature,
+                        current_ma,
+                    }),
+                    (Err(e), _) | (_, Err(e)) => {
+                        error!("Error sampling telemetry: {}", e);
+                        None
+                    }
+                };
+                drop(lens);
+
+                if let Some(sample) = sample {
+                    let mut buf = buffer_for_thread.lock().unwrap();
+                    if capacity > 0 {
+                        if buf.len() >= capacity {
+                            buf.pop_front();
+                        }
+                        buf.push_back(sample);
+                    }
+                }
+
+                let elapsed = tick_start.elapsed();
+                if elapsed < period {
+                    thread::sleep(period - elapsed);
+                }
+            }
+        });
+
+        TelemetryMonitor {
+            stop,
+            handle: Some(handle),
+            buffer,
+        }
+    }
+
+    /// Snapshot the samples currently held in the ring buffer, oldest first.
+    pub fn samples(&self) -> Vec<TelemetrySample> {
+        self.buffer.lock().unwrap().iter().copied().collect()
+    }
+
+    /// Stop the sampling thread and join it.
+    pub fn stop(mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for TelemetryMonitor {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+fn unix_time() -> f64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs_f64())
+        .unwrap_or(0.0)
+}
+
+impl LensDriver {
+    /// Start a background telemetry sampler that polls `get_current`/
+    /// `get_temperature` at `rate_hz` and keeps the last `capacity` samples in
+    /// a ring buffer. Takes `Arc<Mutex<LensDriver>>` so the sampler thread can
+    /// poll the device concurrently with the caller's own foreground use.
+    pub fn start_monitoring(driver: Arc<Mutex<LensDriver>>, rate_hz: f64, capacity: usize) -> TelemetryMonitor {
+        TelemetryMonitor::start(driver, rate_hz, capacity)
+    }
+}
@@ -0,0 +1,167 @@
+use std::f64::consts::PI;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use log::error;
+
+use crate::driver::{LensDriver, LensMode};
+
+/// Periodic waveform shape, evaluated over one cycle as a value in `[-1, 1]`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum WaveformShape {
+    Sine,
+    Triangle,
+    Rectangular,
+    Sawtooth,
+}
+
+impl WaveformShape {
+    fn value(self, phase: f64, duty_cycle: f64) -> f64 {
+        let cycle = phase / (2.0 * PI);
+        match self {
+            WaveformShape::Sine => phase.sin(),
+            WaveformShape::Triangle => 4.0 * (cycle - (cycle + 0.5).floor()).abs() - 1.0,
+            WaveformShape::Rectangular => {
+                if cycle.fract() < duty_cycle {
+                    1.0
+                } else {
+                    -1.0
+                }
+            }
+            WaveformShape::Sawtooth => 2.0 * cycle.fract() - 1.0,
+        }
+    }
+}
+
+/// Configuration for a periodic current/focal-power setpoint sweep.
+#[derive(Clone, Copy, Debug)]
+pub struct WaveformConfig {
+    pub shape: WaveformShape,
+    pub frequency_hz: f64,
+    pub amplitude: f64,
+    pub offset: f64,
+    pub duty_cycle: f64,
+}
+
+const TICK_INTERVAL: Duration = Duration::from_millis(10);
+
+/// A running periodic setpoint generator, driving `set_current`/`set_diopter`
+/// on a dedicated thread at a fixed timestep, for focus sweeps and z-stack
+/// scanning.
+pub struct WaveformGenerator {
+    stop: Arc<AtomicBool>,
+    handle: Option<thread::JoinHandle<()>>,
+}
+
+impl WaveformGenerator {
+    /// Start driving `driver` with `config`. Phase is accumulated from
+    /// elapsed wall-clock time so loop-period drift doesn't distort
+    /// frequency. Each tick's value is clamped to the active mode's legal
+    /// range before being sent.
+    pub fn start(driver: Arc<Mutex<LensDriver>>, config: WaveformConfig) -> Self {
+        let stop = Arc::new(AtomicBool::new(false));
+        let stop_for_thread = stop.clone();
+
+        let handle = thread::spawn(move || {
+            let start_time = Instant::now();
+
+            while !stop_for_thread.load(Ordering::Relaxed) {
+                let elapsed = start_time.elapsed().as_secs_f64();
+                let phase = (2.0 * PI * config.frequency_hz * elapsed).rem_euclid(2.0 * PI);
+                let value = config.offset
+                    + config.amplitude * config.shape.value(phase, config.duty_cycle);
+
+                let mut lens = driver.lock().unwrap();
+                let result = match lens.mode() {
+                    Some(LensMode::Current) => {
+                        let max_current = lens.max_output_current();
+                        lens.set_current(value.clamp(-max_current, max_current))
+                    }
+                    Some(LensMode::FocalPower) => match lens.focal_power_range() {
+                        Some((min_fp, max_fp)) => lens.set_diopter(value.clamp(min_fp, max_fp)),
+                        None => Ok(()),
+                    },
+                    None => Ok(()),
+                };
+                drop(lens);
+
+                if let Err(e) = result {
+                    error!("Error applying waveform setpoint: {}", e);
+                }
+
+                thread::sleep(TICK_INTERVAL);
+            }
+        });
+
+        WaveformGenerator {
+            stop,
+            handle: Some(handle),
+        }
+    }
+
+    /// Stop the generator thread and ramp the output back to zero.
+    pub fn stop(mut self, driver: Arc<Mutex<LensDriver>>) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+
+        let mut lens = driver.lock().unwrap();
+        if let Err(e) = lens.ramp_to_zero(0.2, 10) {
+            error!("Error ramping to zero after stopping waveform: {}", e);
+        }
+    }
+}
+
+impl Drop for WaveformGenerator {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl LensDriver {
+    /// Start a periodic waveform sweep on the active mode's setpoint. The
+    /// generator thread writes setpoints on its own schedule independent of
+    /// the caller, so it needs a shared, lockable handle to the driver rather
+    /// than taking ownership of it.
+    pub fn start_waveform(driver: Arc<Mutex<LensDriver>>, config: WaveformConfig) -> WaveformGenerator {
+        WaveformGenerator::start(driver, config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sine_matches_std_sin() {
+        let phase = 0.37;
+        assert_eq!(WaveformShape::Sine.value(phase, 0.5), phase.sin());
+    }
+
+    #[test]
+    fn triangle_peaks_at_half_cycle_and_troughs_at_start() {
+        let half = 2.0 * PI * 0.5;
+        assert!((WaveformShape::Triangle.value(half, 0.5) - 1.0).abs() < 1e-9);
+        assert!((WaveformShape::Triangle.value(0.0, 0.5) - (-1.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn rectangular_respects_duty_cycle() {
+        let just_inside = 2.0 * PI * 0.1;
+        let just_outside = 2.0 * PI * 0.6;
+        assert_eq!(WaveformShape::Rectangular.value(just_inside, 0.5), 1.0);
+        assert_eq!(WaveformShape::Rectangular.value(just_outside, 0.5), -1.0);
+    }
+
+    #[test]
+    fn sawtooth_ramps_linearly_over_one_cycle() {
+        assert!((WaveformShape::Sawtooth.value(0.0, 0.5) - (-1.0)).abs() < 1e-9);
+        assert!((WaveformShape::Sawtooth.value(PI, 0.5) - 0.0).abs() < 1e-9);
+    }
+}